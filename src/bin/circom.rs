@@ -0,0 +1,248 @@
+use bellman::{groth16, Circuit, ConstraintSystem, SynthesisError, Variable};
+use pairing::{bls12_381::Bls12, Engine};
+use rand::rngs::OsRng;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    path::{Path, PathBuf},
+};
+use structopt::StructOpt;
+use tubular_bells::parse_field;
+
+/// One side of an R1CS constraint: maps a variable index to its coefficient
+/// rendered as a decimal string (the form circom exports).
+type LinearTerms = HashMap<usize, String>;
+/// A single R1CS constraint `A * B = C`, as its three sides `(A, B, C)`.
+type Constraint = (LinearTerms, LinearTerms, LinearTerms);
+
+/// The R1CS description exported from circom.
+#[derive(Deserialize)]
+struct R1csFile {
+    #[serde(rename = "nPubInputs")]
+    num_pub_inputs: usize,
+    #[serde(rename = "nOutputs")]
+    num_outputs: usize,
+    #[serde(rename = "nVars")]
+    num_vars: usize,
+    constraints: Vec<Constraint>,
+}
+
+/// A generic `bellman::Circuit` synthesized from a circom-exported R1CS. Variable 0
+/// is the constant `one` wire; the following `num_inputs + num_outputs` variables
+/// are public, and the remainder are private witness variables.
+pub struct CircomCircuit<E: Engine> {
+    pub num_inputs: usize,
+    pub num_outputs: usize,
+    pub num_variables: usize,
+    pub constraints: Vec<Constraint>,
+    pub witness: Option<Vec<E::Fr>>,
+}
+
+impl<E: Engine> CircomCircuit<E> {
+    fn new(r1cs: R1csFile, witness: Option<Vec<E::Fr>>) -> Self {
+        Self {
+            num_inputs: r1cs.num_pub_inputs,
+            num_outputs: r1cs.num_outputs,
+            num_variables: r1cs.num_vars,
+            constraints: r1cs.constraints,
+            witness,
+        }
+    }
+
+    /// Index of the last public (input) variable; everything above is private.
+    fn num_public(&self) -> usize {
+        self.num_inputs + self.num_outputs
+    }
+}
+
+impl<E: Engine> Circuit<E> for CircomCircuit<E> {
+    fn synthesize<CS: ConstraintSystem<E>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
+        let num_public = self.num_public();
+
+        // Variable 0 is the constant `one` wire.
+        let mut variables: Vec<Variable> = Vec::with_capacity(self.num_variables);
+        variables.push(CS::one());
+
+        for i in 1..self.num_variables {
+            let value = || {
+                self.witness
+                    .as_ref()
+                    .map(|w| w[i])
+                    .ok_or(SynthesisError::AssignmentMissing)
+            };
+            let var = if i <= num_public {
+                cs.alloc_input(|| format!("input {}", i), value)?
+            } else {
+                cs.alloc(|| format!("aux {}", i), value)?
+            };
+            variables.push(var);
+        }
+
+        let build = |terms: &LinearTerms, mut lc: bellman::LinearCombination<E>| {
+            for (index, coeff) in terms {
+                let coeff = parse_field::<E::Fr>(coeff).expect("invalid coefficient");
+                lc = lc + (coeff, variables[*index]);
+            }
+            lc
+        };
+
+        for (i, (a, b, c)) in self.constraints.iter().enumerate() {
+            cs.enforce(
+                || format!("constraint {}", i),
+                |lc| build(a, lc),
+                |lc| build(b, lc),
+                |lc| build(c, lc),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(StructOpt)]
+enum Opt {
+    /// Generates public parameters for a circom circuit.
+    GenerateParams {
+        #[structopt(long, parse(from_os_str))]
+        circuit: PathBuf,
+
+        #[structopt(long, parse(from_os_str), default_value = "params")]
+        params: PathBuf,
+
+        #[structopt(long, parse(from_os_str), default_value = "vk")]
+        vk: PathBuf,
+    },
+
+    /// Generates a proof from a circuit and its witness.
+    GenerateProof {
+        #[structopt(long, parse(from_os_str))]
+        circuit: PathBuf,
+
+        #[structopt(long, parse(from_os_str))]
+        witness: PathBuf,
+
+        #[structopt(long, parse(from_os_str), default_value = "params")]
+        params: PathBuf,
+
+        #[structopt(long, parse(from_os_str), default_value = "proof")]
+        proof: PathBuf,
+    },
+
+    /// Verifies a proof against the circuit's public inputs.
+    VerifyProof {
+        #[structopt(long, parse(from_os_str))]
+        circuit: PathBuf,
+
+        #[structopt(long, parse(from_os_str))]
+        witness: PathBuf,
+
+        #[structopt(long, parse(from_os_str), default_value = "proof")]
+        proof: PathBuf,
+
+        #[structopt(long, parse(from_os_str), default_value = "vk")]
+        vk: PathBuf,
+    },
+}
+
+fn read_r1cs<P: AsRef<Path>>(path: P) -> anyhow::Result<R1csFile> {
+    Ok(serde_json::from_reader(File::open(path)?)?)
+}
+
+fn read_witness<E: Engine, P: AsRef<Path>>(path: P) -> anyhow::Result<Vec<E::Fr>> {
+    let raw: Vec<String> = serde_json::from_reader(File::open(path)?)?;
+    raw.iter()
+        .map(|s| parse_field::<E::Fr>(s))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| anyhow::anyhow!("witness contains a non-decimal entry"))
+}
+
+fn generate_params(circuit: PathBuf, params: PathBuf, vk: PathBuf) -> anyhow::Result<()> {
+    println!("Generating parameters...");
+
+    let mut rng = OsRng;
+    let r1cs = read_r1cs(&circuit)?;
+    let circuit = CircomCircuit::<Bls12>::new(r1cs, None);
+    let p = groth16::generate_random_parameters::<Bls12, _, _>(circuit, &mut rng)?;
+
+    p.write(File::create(params)?)?;
+    p.vk.write(File::create(vk)?)?;
+
+    Ok(())
+}
+
+fn generate_proof(
+    circuit: PathBuf,
+    witness: PathBuf,
+    params: PathBuf,
+    proof: PathBuf,
+) -> anyhow::Result<()> {
+    let params = groth16::Parameters::<Bls12>::read(File::open(params)?, true)?;
+
+    println!("Creating proof...");
+
+    let r1cs = read_r1cs(&circuit)?;
+    let witness = read_witness::<Bls12, _>(&witness)?;
+    let circuit = CircomCircuit::<Bls12>::new(r1cs, Some(witness));
+
+    let mut rng = OsRng;
+    let p = groth16::create_random_proof(circuit, &params, &mut rng)?;
+
+    let f_proof = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(proof)?;
+    p.write(f_proof)?;
+
+    Ok(())
+}
+
+fn verify_proof(
+    circuit: PathBuf,
+    witness: PathBuf,
+    proof: PathBuf,
+    vk: PathBuf,
+) -> anyhow::Result<()> {
+    println!("Loading verification key and proof...");
+
+    let vk = groth16::VerifyingKey::<Bls12>::read(File::open(vk)?)?;
+    let proof = groth16::Proof::read(File::open(proof)?)?;
+    let pvk = groth16::prepare_verifying_key(&vk);
+
+    let r1cs = read_r1cs(&circuit)?;
+    let num_public = r1cs.num_pub_inputs + r1cs.num_outputs;
+    let witness = read_witness::<Bls12, _>(&witness)?;
+    // The public inputs are the public portion of the witness (excluding the
+    // constant `one` wire at index 0).
+    let inputs = witness[1..=num_public].to_vec();
+
+    println!("Verifying proof...");
+
+    let verified = groth16::verify_proof(&pvk, &proof, &inputs)?;
+    println!("Proof successfully verified? {}", verified);
+
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    match Opt::from_args() {
+        Opt::GenerateParams {
+            circuit,
+            params,
+            vk,
+        } => generate_params(circuit, params, vk),
+        Opt::GenerateProof {
+            circuit,
+            witness,
+            params,
+            proof,
+        } => generate_proof(circuit, witness, params, proof),
+        Opt::VerifyProof {
+            circuit,
+            witness,
+            proof,
+            vk,
+        } => verify_proof(circuit, witness, proof, vk),
+    }
+}
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use bellman::{
     groth16::{
         create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof,
@@ -5,12 +7,10 @@ use bellman::{
     Circuit, ConstraintSystem, SynthesisError,
 };
 use ff::Field;
-use pairing::{
-    bls12_381::{Bls12, Fr},
-    Engine,
-};
+use pairing::{bls12_381::Bls12, bn256::Bn256, Engine};
 use rand::rngs::OsRng;
-use tubular_bells::get_constant;
+use structopt::StructOpt;
+use tubular_bells::parse_field;
 
 pub struct CubeCircuit<E: Engine> {
     pub x: Option<E::Fr>,
@@ -60,7 +60,7 @@ impl<E: Engine> Circuit<E> for CubeCircuit<E> {
         cs.enforce(|| "z2", |lc| lc + z1, |lc| lc + x, |lc| lc + z2);
 
         // alloc z2 + x + 5 = y
-        let constant = get_constant::<E::Fr>(5);
+        let constant = parse_field::<E::Fr>("5").unwrap();
         let y = cs.alloc_input(
             || "y",
             || {
@@ -81,13 +81,38 @@ impl<E: Engine> Circuit<E> for CubeCircuit<E> {
     }
 }
 
-fn main() -> anyhow::Result<()> {
+/// The pairing curve the proof targets. `bn256` matches the verifier exposed by the
+/// EVM precompiles, while `bls12-381` is the default used elsewhere in this crate.
+enum Curve {
+    Bls12_381,
+    Bn256,
+}
+
+impl FromStr for Curve {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bls12-381" => Ok(Curve::Bls12_381),
+            "bn256" => Ok(Curve::Bn256),
+            other => Err(anyhow::anyhow!("unknown curve: {}", other)),
+        }
+    }
+}
+
+#[derive(StructOpt)]
+struct Opt {
+    #[structopt(long, default_value = "bls12-381")]
+    curve: Curve,
+}
+
+fn run<E: Engine>() -> anyhow::Result<()> {
     let mut rng = OsRng;
 
     println!("Creating parameters...");
 
-    let circuit = CubeCircuit::<Bls12>::default();
-    let params = generate_random_parameters(circuit, &mut rng)?;
+    let circuit = CubeCircuit::<E>::default();
+    let params = generate_random_parameters::<E, _, _>(circuit, &mut rng)?;
 
     println!("Preparing verification key...");
 
@@ -95,7 +120,7 @@ fn main() -> anyhow::Result<()> {
 
     println!("Creating proofs...");
 
-    let circuit = CubeCircuit::<Bls12>::new(get_constant::<Fr>(3));
+    let circuit = CubeCircuit::<E>::new(parse_field::<E::Fr>("3").unwrap());
 
     println!("Creating groth16 proof with parameters...");
 
@@ -103,9 +128,16 @@ fn main() -> anyhow::Result<()> {
 
     println!("Verifying proof...");
 
-    let verified = verify_proof(&pvk, &proof, &[get_constant::<Fr>(35)])?;
+    let verified = verify_proof(&pvk, &proof, &[parse_field::<E::Fr>("35").unwrap()])?;
 
     println!("Proof successfully verified? {}", verified);
 
     Ok(())
 }
+
+fn main() -> anyhow::Result<()> {
+    match Opt::from_args().curve {
+        Curve::Bls12_381 => run::<Bls12>(),
+        Curve::Bn256 => run::<Bn256>(),
+    }
+}
@@ -6,13 +6,15 @@ use bellman::{
     },
     groth16, Circuit, ConstraintSystem, SynthesisError,
 };
-use pairing::{bls12_381::Bls12, Engine};
+use pairing::{bls12_381::Bls12, bn256::Bn256, Engine};
 use rand::rngs::OsRng;
 use std::{
     fs::{self, File, OpenOptions},
     path::{Path, PathBuf},
+    str::FromStr,
 };
 use structopt::StructOpt;
+use tubular_bells::parse_field;
 
 fn sha256d<E: Engine, CS: ConstraintSystem<E>>(
     mut cs: CS,
@@ -37,35 +39,43 @@ fn sha256d<E: Engine, CS: ConstraintSystem<E>>(
 }
 
 struct HashCircuit {
-    preimage: Option<[u8; 80]>,
+    preimage: Option<Vec<u8>>,
+    /// Length of the preimage in bytes. Fixes the number of allocated bits, and is
+    /// exposed as a public input so the statement is bound to a single message size.
+    len: usize,
 }
 
 impl HashCircuit {
-    fn new(preimage: [u8; 80]) -> Self {
-        let preimage = Some(preimage);
-        Self { preimage }
+    fn new(preimage: Vec<u8>) -> Self {
+        let len = preimage.len();
+        Self {
+            preimage: Some(preimage),
+            len,
+        }
     }
-}
 
-impl Default for HashCircuit {
-    fn default() -> Self {
-        Self { preimage: None }
+    fn empty(len: usize) -> Self {
+        Self {
+            preimage: None,
+            len,
+        }
     }
 }
 
 impl<E: Engine> Circuit<E> for HashCircuit {
     fn synthesize<CS: ConstraintSystem<E>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
-        let bit_values = if let Some(preimage) = self.preimage {
+        let bit_values: Vec<Option<bool>> = if let Some(preimage) = self.preimage {
+            assert_eq!(preimage.len(), self.len);
             preimage
                 .iter()
                 .map(|byte| (0..8).map(move |i| (byte >> i) & 1u8 == 1u8))
                 .flatten()
-                .map(|b| Some(b))
+                .map(Some)
                 .collect()
         } else {
-            vec![None; 80 * 8]
+            vec![None; self.len * 8]
         };
-        assert_eq!(bit_values.len(), 80 * 8);
+        assert_eq!(bit_values.len(), self.len * 8);
 
         let preimage_bits = bit_values
             .into_iter()
@@ -76,22 +86,94 @@ impl<E: Engine> Circuit<E> for HashCircuit {
 
         let hash = sha256d(cs.namespace(|| "SHA-256d(preimage)"), &preimage_bits)?;
 
-        multipack::pack_into_inputs(cs.namespace(|| "pack hash"), &hash)
+        multipack::pack_into_inputs(cs.namespace(|| "pack hash"), &hash)?;
+
+        // Bind the message byte-length into the public statement. The length is a
+        // circuit constant (the preimage bit-count is fixed at parameter-generation
+        // time), so constraining a public input to equal it prevents a prover from
+        // claiming a different padded length than the one the verifier expects.
+        let len = parse_field::<E::Fr>(&self.len.to_string()).ok_or(SynthesisError::Unsatisfiable)?;
+        let len_input = cs.alloc_input(|| "preimage length", || Ok(len))?;
+        cs.enforce(
+            || "preimage length is fixed",
+            |lc| lc + (len, CS::one()),
+            |lc| lc + CS::one(),
+            |lc| lc + len_input,
+        );
+
+        Ok(())
+    }
+}
+
+/// The pairing curve the proof targets. `bn256` matches the verifier exposed by the
+/// EVM precompiles, while `bls12-381` is the default used elsewhere in this crate.
+enum Curve {
+    Bls12_381,
+    Bn256,
+}
+
+impl FromStr for Curve {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bls12-381" => Ok(Curve::Bls12_381),
+            "bn256" => Ok(Curve::Bn256),
+            other => Err(anyhow::anyhow!("unknown curve: {}", other)),
+        }
+    }
+}
+
+/// On-disk encoding of proofs and verifying keys. `bin` is the opaque groth16
+/// serialization; `json` is the structured hex form for cross-language verifiers.
+#[derive(Clone, Copy)]
+enum Format {
+    Bin,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bin" => Ok(Format::Bin),
+            "json" => Ok(Format::Json),
+            other => Err(anyhow::anyhow!("unknown format: {}", other)),
+        }
     }
 }
 
 #[derive(StructOpt)]
 enum Opt {
     /// Generates public parameters.
-    GenerateParams,
+    GenerateParams {
+        #[structopt(long, default_value = "80")]
+        preimage_len: usize,
+
+        #[structopt(long, default_value = "bls12-381")]
+        curve: Curve,
+
+        #[structopt(long, default_value = "bin")]
+        format: Format,
+    },
 
     /// Generates proof.
     GenerateProof {
         #[structopt(parse(from_os_str))]
         preimage: PathBuf,
 
+        #[structopt(long, default_value = "80")]
+        preimage_len: usize,
+
         #[structopt(long, parse(from_os_str), default_value = "params")]
         params: PathBuf,
+
+        #[structopt(long, default_value = "bls12-381")]
+        curve: Curve,
+
+        #[structopt(long, default_value = "bin")]
+        format: Format,
     },
 
     /// Verifies the proof using the generated verification
@@ -99,59 +181,78 @@ enum Opt {
     VerifyProof {
         hash: String,
 
+        #[structopt(long, default_value = "80")]
+        preimage_len: usize,
+
         #[structopt(long, parse(from_os_str), default_value = "proof")]
         proof: PathBuf,
 
         #[structopt(long, parse(from_os_str), default_value = "vk")]
         vk: PathBuf,
+
+        #[structopt(long, default_value = "bls12-381")]
+        curve: Curve,
+
+        #[structopt(long, default_value = "bin")]
+        format: Format,
     },
 }
 
-fn generate_params() -> anyhow::Result<()> {
+fn write_vk<E: Engine>(vk: &groth16::VerifyingKey<E>, format: Format) -> anyhow::Result<()> {
+    let f_vk = File::create("vk")?;
+    match format {
+        Format::Bin => vk.write(f_vk)?,
+        Format::Json => serde_json::to_writer_pretty(f_vk, &tubular_bells::json::vk_to_json(vk))?,
+    }
+    Ok(())
+}
+
+fn generate_params<E: Engine>(preimage_len: usize, format: Format) -> anyhow::Result<()> {
     if let Ok(f) = File::open("params") {
         if Path::new("vk").exists() {
             return Ok(());
         }
 
-        let params = groth16::Parameters::<Bls12>::read(f, true)?;
-        let f_vk = File::create("vk")?;
-        params.vk.write(f_vk)?;
+        let params = groth16::Parameters::<E>::read(f, true)?;
+        write_vk(&params.vk, format)?;
     }
 
     println!("Generating parameters...");
 
     let mut rng = OsRng;
-    let circuit = HashCircuit::default();
-    let params = groth16::generate_random_parameters::<Bls12, _, _>(circuit, &mut rng)?;
+    let circuit = HashCircuit::empty(preimage_len);
+    let params = groth16::generate_random_parameters::<E, _, _>(circuit, &mut rng)?;
 
     let f_params = File::create("params")?;
-    let f_vk = File::create("vk")?;
     params.write(f_params)?;
-    params.vk.write(f_vk)?;
+    write_vk(&params.vk, format)?;
 
     Ok(())
 }
 
-fn generate_proof<P1: AsRef<Path>, P2: AsRef<Path>>(
+fn generate_proof<E: Engine, P1: AsRef<Path>, P2: AsRef<Path>>(
     preimage: P1,
+    preimage_len: usize,
     params: P2,
+    format: Format,
 ) -> anyhow::Result<()> {
     use sha2::{Digest, Sha256};
 
     let f_params = File::open(params.as_ref())?;
-    let params = groth16::Parameters::<Bls12>::read(f_params, true)?;
+    let params = groth16::Parameters::<E>::read(f_params, true)?;
 
     println!("Creating proofs...");
 
     let preimage = fs::read(preimage)?;
-    let mut preimage_truncated = [0u8; 80];
-    for (i, byte) in preimage.into_iter().enumerate() {
-        if i == 80 {
-            break;
-        }
-        preimage_truncated[i] = byte;
+    if preimage.len() != preimage_len {
+        anyhow::bail!(
+            "preimage is {} bytes but --preimage-len is {}",
+            preimage.len(),
+            preimage_len
+        );
     }
-    let circuit = HashCircuit::new(preimage_truncated);
+    let digest = base64::encode(Sha256::digest(&preimage));
+    let circuit = HashCircuit::new(preimage);
 
     println!("Creating groth16 proof with parameters...");
 
@@ -163,30 +264,45 @@ fn generate_proof<P1: AsRef<Path>, P2: AsRef<Path>>(
         .create(true)
         .truncate(true)
         .open("proof")?;
-    proof.write(f_proof)?;
+    match format {
+        Format::Bin => proof.write(f_proof)?,
+        Format::Json => {
+            serde_json::to_writer_pretty(f_proof, &tubular_bells::json::proof_to_json(&proof))?
+        }
+    }
 
-    println!(
-        "Digest: {}",
-        base64::encode(Sha256::digest(&preimage_truncated))
-    );
+    println!("Digest: {}", digest);
 
     Ok(())
 }
 
-fn verify_proof<S: AsRef<str>, P1: AsRef<Path>, P2: AsRef<Path>>(
+fn verify_proof<E: Engine, S: AsRef<str>, P1: AsRef<Path>, P2: AsRef<Path>>(
     hash: S,
+    preimage_len: usize,
     proof: P1,
     vk: P2,
+    format: Format,
 ) -> anyhow::Result<()> {
     use sha2::{Digest, Sha256};
 
     println!("Loading verification key and proof...");
 
     let f_vk = File::open(vk)?;
-    let vk = groth16::VerifyingKey::<Bls12>::read(f_vk)?;
-
     let f_proof = File::open(proof)?;
-    let proof = groth16::Proof::read(f_proof)?;
+    let (vk, proof) = match format {
+        Format::Bin => (
+            groth16::VerifyingKey::<E>::read(f_vk)?,
+            groth16::Proof::read(f_proof)?,
+        ),
+        Format::Json => {
+            let vk_json: tubular_bells::json::VerifyingKeyJson = serde_json::from_reader(f_vk)?;
+            let proof_json: tubular_bells::json::ProofJson = serde_json::from_reader(f_proof)?;
+            (
+                tubular_bells::json::vk_from_json::<E>(&vk_json)?,
+                tubular_bells::json::proof_from_json::<E>(&proof_json)?,
+            )
+        }
+    };
 
     let pvk = groth16::prepare_verifying_key(&vk);
 
@@ -197,7 +313,9 @@ fn verify_proof<S: AsRef<str>, P1: AsRef<Path>, P2: AsRef<Path>>(
     let hash = Sha256::digest(&hash);
 
     let hash_bits = multipack::bytes_to_bits_le(&hash);
-    let inputs = multipack::compute_multipacking::<Bls12>(&hash_bits);
+    let mut inputs = multipack::compute_multipacking::<E>(&hash_bits);
+    // The trailing public input is the preimage byte-length (see `synthesize`).
+    inputs.push(parse_field::<E::Fr>(&preimage_len.to_string()).unwrap());
     let verified = groth16::verify_proof(&pvk, &proof, &inputs)?;
 
     println!("Proof successfully verified? {}", verified);
@@ -208,8 +326,36 @@ fn verify_proof<S: AsRef<str>, P1: AsRef<Path>, P2: AsRef<Path>>(
 fn main() -> anyhow::Result<()> {
     let opt = Opt::from_args();
     match opt {
-        Opt::GenerateParams => generate_params(),
-        Opt::GenerateProof { preimage, params } => generate_proof(preimage, params),
-        Opt::VerifyProof { hash, proof, vk } => verify_proof(hash, proof, vk),
+        Opt::GenerateParams {
+            preimage_len,
+            curve,
+            format,
+        } => match curve {
+            Curve::Bls12_381 => generate_params::<Bls12>(preimage_len, format),
+            Curve::Bn256 => generate_params::<Bn256>(preimage_len, format),
+        },
+        Opt::GenerateProof {
+            preimage,
+            preimage_len,
+            params,
+            curve,
+            format,
+        } => match curve {
+            Curve::Bls12_381 => {
+                generate_proof::<Bls12, _, _>(preimage, preimage_len, params, format)
+            }
+            Curve::Bn256 => generate_proof::<Bn256, _, _>(preimage, preimage_len, params, format),
+        },
+        Opt::VerifyProof {
+            hash,
+            preimage_len,
+            proof,
+            vk,
+            curve,
+            format,
+        } => match curve {
+            Curve::Bls12_381 => verify_proof::<Bls12, _, _, _>(hash, preimage_len, proof, vk, format),
+            Curve::Bn256 => verify_proof::<Bn256, _, _, _>(hash, preimage_len, proof, vk, format),
+        },
     }
 }
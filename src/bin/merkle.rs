@@ -0,0 +1,255 @@
+use bellman::{
+    gadgets::{
+        boolean::{AllocatedBit, Boolean},
+        multipack,
+        sha256::sha256,
+    },
+    groth16::{
+        create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof,
+    },
+    Circuit, ConstraintSystem, SynthesisError,
+};
+use pairing::{bls12_381::Bls12, Engine};
+use rand::rngs::OsRng;
+
+/// Domain-separation prefix for note commitments (the byte `0b10110000`), mirroring
+/// the Sprout `NoteCommit` tag. Bits are laid out most-significant first so the
+/// in-circuit preimage matches a plain SHA-256 over the equivalent byte string.
+const NOTE_COMMITMENT_PREFIX: u8 = 0b1011_0000;
+
+/// Builds the note-commitment preimage as a fixed bit layout and hashes it with
+/// SHA-256: the 8-bit domain-separation prefix, the 256-bit `a_pk`, the 256-bit
+/// `rho`, the 64-bit little-endian `value`, `rho` again, and the 256-bit randomness
+/// `r`. All operands are expected most-significant bit first.
+fn note_commitment<E: Engine, CS: ConstraintSystem<E>>(
+    mut cs: CS,
+    a_pk: &[Boolean],
+    rho: &[Boolean],
+    value: &[Boolean],
+    r: &[Boolean],
+) -> Result<Vec<Boolean>, SynthesisError> {
+    let mut preimage = Vec::with_capacity(8 + 256 + 256 + 64 + 256 + 256);
+    for i in (0..8).rev() {
+        preimage.push(Boolean::constant((NOTE_COMMITMENT_PREFIX >> i) & 1 == 1));
+    }
+    preimage.extend_from_slice(a_pk);
+    preimage.extend_from_slice(rho);
+    preimage.extend_from_slice(value);
+    preimage.extend_from_slice(rho);
+    preimage.extend_from_slice(r);
+
+    sha256(cs.namespace(|| "note commitment"), &preimage)
+}
+
+/// Selects `a` when `cond` is set and `b` otherwise. The two conjuncts are mutually
+/// exclusive, so their disjunction collapses to an XOR.
+fn select<E: Engine, CS: ConstraintSystem<E>>(
+    mut cs: CS,
+    cond: &Boolean,
+    a: &Boolean,
+    b: &Boolean,
+) -> Result<Boolean, SynthesisError> {
+    let lhs = Boolean::and(cs.namespace(|| "a and cond"), a, cond)?;
+    let rhs = Boolean::and(cs.namespace(|| "b and not cond"), b, &cond.not())?;
+    Boolean::xor(cs.namespace(|| "select"), &lhs, &rhs)
+}
+
+fn witness_bits<E: Engine, CS: ConstraintSystem<E>>(
+    mut cs: CS,
+    bytes: Option<&[u8]>,
+    num_bytes: usize,
+) -> Result<Vec<Boolean>, SynthesisError> {
+    let values: Vec<Option<bool>> = match bytes {
+        Some(bytes) => {
+            assert_eq!(bytes.len(), num_bytes);
+            bytes
+                .iter()
+                .flat_map(|byte| (0..8).rev().map(move |i| Some((byte >> i) & 1 == 1)))
+                .collect()
+        }
+        None => vec![None; num_bytes * 8],
+    };
+
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(i, b)| AllocatedBit::alloc(cs.namespace(|| format!("bit {}", i)), b))
+        .map(|b| b.map(Boolean::from))
+        .collect()
+}
+
+/// A single level of a Merkle authentication path: the sibling hash and whether the
+/// current node sits on the right of its parent.
+#[derive(Clone)]
+struct PathNode {
+    sibling: Option<[u8; 32]>,
+    is_right: Option<bool>,
+}
+
+/// Proves knowledge of a note whose commitment is a leaf of a Merkle tree with a
+/// publicly-exposed root. The note fields and the authentication path are private;
+/// the root is the only public input.
+struct MerkleCircuit {
+    a_pk: Option<[u8; 32]>,
+    rho: Option<[u8; 32]>,
+    value: Option<u64>,
+    r: Option<[u8; 32]>,
+    auth_path: Vec<PathNode>,
+}
+
+impl MerkleCircuit {
+    fn empty(depth: usize) -> Self {
+        Self {
+            a_pk: None,
+            rho: None,
+            value: None,
+            r: None,
+            auth_path: vec![
+                PathNode {
+                    sibling: None,
+                    is_right: None,
+                };
+                depth
+            ],
+        }
+    }
+}
+
+impl<E: Engine> Circuit<E> for MerkleCircuit {
+    fn synthesize<CS: ConstraintSystem<E>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
+        let value_bytes = self.value.map(|v| v.to_le_bytes());
+
+        let a_pk = witness_bits(cs.namespace(|| "a_pk"), self.a_pk.as_ref().map(|b| &b[..]), 32)?;
+        let rho = witness_bits(cs.namespace(|| "rho"), self.rho.as_ref().map(|b| &b[..]), 32)?;
+        let value = witness_bits(
+            cs.namespace(|| "value"),
+            value_bytes.as_ref().map(|b| &b[..]),
+            8,
+        )?;
+        let r = witness_bits(cs.namespace(|| "r"), self.r.as_ref().map(|b| &b[..]), 32)?;
+
+        let mut cur = note_commitment(cs.namespace(|| "commitment"), &a_pk, &rho, &value, &r)?;
+
+        for (i, node) in self.auth_path.into_iter().enumerate() {
+            let cs = &mut cs.namespace(|| format!("merkle level {}", i));
+
+            let sibling = witness_bits(
+                cs.namespace(|| "sibling"),
+                node.sibling.as_ref().map(|b| &b[..]),
+                32,
+            )?;
+            let is_right = Boolean::from(AllocatedBit::alloc(
+                cs.namespace(|| "is_right"),
+                node.is_right,
+            )?);
+
+            let mut preimage = Vec::with_capacity(512);
+            for (j, (c, s)) in cur.iter().zip(sibling.iter()).enumerate() {
+                preimage.push(select(
+                    cs.namespace(|| format!("left bit {}", j)),
+                    &is_right,
+                    s,
+                    c,
+                )?);
+            }
+            for (j, (c, s)) in cur.iter().zip(sibling.iter()).enumerate() {
+                preimage.push(select(
+                    cs.namespace(|| format!("right bit {}", j)),
+                    &is_right,
+                    c,
+                    s,
+                )?);
+            }
+
+            cur = sha256(cs.namespace(|| "parent"), &preimage)?;
+        }
+
+        multipack::pack_into_inputs(cs.namespace(|| "pack root"), &cur)
+    }
+}
+
+/// Host-side mirror of the `note_commitment` gadget, used to derive the public root.
+fn compute_note_commitment(a_pk: &[u8; 32], rho: &[u8; 32], value: u64, r: &[u8; 32]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update([NOTE_COMMITMENT_PREFIX]);
+    hasher.update(a_pk);
+    hasher.update(rho);
+    hasher.update(value.to_le_bytes());
+    hasher.update(rho);
+    hasher.update(r);
+    hasher.finalize().into()
+}
+
+/// Host-side mirror of the Merkle path, folding the leaf up to the root.
+fn compute_root(leaf: [u8; 32], path: &[(/* sibling */ [u8; 32], /* is_right */ bool)]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+
+    let mut cur = leaf;
+    for (sibling, is_right) in path {
+        let (left, right) = if *is_right {
+            (sibling, &cur)
+        } else {
+            (&cur, sibling)
+        };
+        let mut hasher = Sha256::new();
+        hasher.update(left);
+        hasher.update(right);
+        cur = hasher.finalize().into();
+    }
+    cur
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut rng = OsRng;
+
+    // A small fixed witness for the demonstration.
+    let a_pk = [1u8; 32];
+    let rho = [2u8; 32];
+    let value = 42u64;
+    let r = [3u8; 32];
+    let path = vec![([7u8; 32], false), ([9u8; 32], true)];
+
+    println!("Creating parameters...");
+
+    let params = {
+        let circuit = MerkleCircuit::empty(path.len());
+        generate_random_parameters::<Bls12, _, _>(circuit, &mut rng)?
+    };
+
+    let pvk = prepare_verifying_key(&params.vk);
+
+    println!("Creating proof...");
+
+    let leaf = compute_note_commitment(&a_pk, &rho, value, &r);
+    let root = compute_root(leaf, &path);
+
+    let circuit = MerkleCircuit {
+        a_pk: Some(a_pk),
+        rho: Some(rho),
+        value: Some(value),
+        r: Some(r),
+        auth_path: path
+            .iter()
+            .map(|(sibling, is_right)| PathNode {
+                sibling: Some(*sibling),
+                is_right: Some(*is_right),
+            })
+            .collect(),
+    };
+    let proof = create_random_proof(circuit, &params, &mut rng)?;
+
+    println!("Verifying proof...");
+
+    let root_bits: Vec<bool> = root
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+        .collect();
+    let inputs = multipack::compute_multipacking::<Bls12>(&root_bits);
+    let verified = verify_proof(&pvk, &proof, &inputs)?;
+
+    println!("Proof successfully verified? {}", verified);
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+//! Structured JSON/hex serialization of groth16 proofs and verifying keys for
+//! cross-language verification (e.g. JS/Solidity). Each affine point is decomposed
+//! into its field-element coordinates, rendered big-endian as `0x`-prefixed hex
+//! strings, matching the shape snark tooling commonly consumes.
+
+use bellman::groth16::{Proof, VerifyingKey};
+use pairing::{CurveAffine, EncodedPoint, Engine};
+use serde::{Deserialize, Serialize};
+
+/// JSON representation of a `groth16::Proof`. `a`/`c` are G1 points (two
+/// coordinates), `b` is a G2 point (four coordinates).
+#[derive(Serialize, Deserialize)]
+pub struct ProofJson {
+    pub protocol: String,
+    pub a: Vec<String>,
+    pub b: Vec<String>,
+    pub c: Vec<String>,
+}
+
+/// JSON representation of a `groth16::VerifyingKey`. Only the verification-relevant
+/// points are exported; the proving-only `beta_g1`/`delta_g1` are reconstructed as
+/// the identity on import.
+#[derive(Serialize, Deserialize)]
+pub struct VerifyingKeyJson {
+    pub protocol: String,
+    pub alpha_g1: Vec<String>,
+    pub beta_g2: Vec<String>,
+    pub gamma_g2: Vec<String>,
+    pub delta_g2: Vec<String>,
+    pub ic: Vec<Vec<String>>,
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(2 + bytes.len() * 2);
+    s.push_str("0x");
+    for b in bytes {
+        s.push_str(&format!("{:02x}", b));
+    }
+    s
+}
+
+fn hex_decode(s: &str) -> anyhow::Result<Vec<u8>> {
+    let s = s.trim_start_matches("0x");
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(Into::into))
+        .collect()
+}
+
+fn point_to_hex<P: EncodedPoint>(enc: P, coords: usize) -> Vec<String> {
+    let bytes = enc.as_ref();
+    let chunk = bytes.len() / coords;
+    bytes.chunks(chunk).map(hex_encode).collect()
+}
+
+fn g1_to_hex<E: Engine>(p: &E::G1Affine) -> Vec<String> {
+    point_to_hex(p.into_uncompressed(), 2)
+}
+
+fn g2_to_hex<E: Engine>(p: &E::G2Affine) -> Vec<String> {
+    point_to_hex(p.into_uncompressed(), 4)
+}
+
+fn g1_from_hex<E: Engine>(parts: &[String]) -> anyhow::Result<E::G1Affine> {
+    let mut enc = <E::G1Affine as CurveAffine>::Uncompressed::empty();
+    let bytes: Vec<u8> = parts
+        .iter()
+        .map(|s| hex_decode(s))
+        .collect::<anyhow::Result<Vec<_>>>()?
+        .concat();
+    enc.as_mut().copy_from_slice(&bytes);
+    Ok(enc.into_affine()?)
+}
+
+fn g2_from_hex<E: Engine>(parts: &[String]) -> anyhow::Result<E::G2Affine> {
+    let mut enc = <E::G2Affine as CurveAffine>::Uncompressed::empty();
+    let bytes: Vec<u8> = parts
+        .iter()
+        .map(|s| hex_decode(s))
+        .collect::<anyhow::Result<Vec<_>>>()?
+        .concat();
+    enc.as_mut().copy_from_slice(&bytes);
+    Ok(enc.into_affine()?)
+}
+
+pub fn proof_to_json<E: Engine>(proof: &Proof<E>) -> ProofJson {
+    ProofJson {
+        protocol: "groth16".to_string(),
+        a: g1_to_hex::<E>(&proof.a),
+        b: g2_to_hex::<E>(&proof.b),
+        c: g1_to_hex::<E>(&proof.c),
+    }
+}
+
+pub fn proof_from_json<E: Engine>(json: &ProofJson) -> anyhow::Result<Proof<E>> {
+    Ok(Proof {
+        a: g1_from_hex::<E>(&json.a)?,
+        b: g2_from_hex::<E>(&json.b)?,
+        c: g1_from_hex::<E>(&json.c)?,
+    })
+}
+
+pub fn vk_to_json<E: Engine>(vk: &VerifyingKey<E>) -> VerifyingKeyJson {
+    VerifyingKeyJson {
+        protocol: "groth16".to_string(),
+        alpha_g1: g1_to_hex::<E>(&vk.alpha_g1),
+        beta_g2: g2_to_hex::<E>(&vk.beta_g2),
+        gamma_g2: g2_to_hex::<E>(&vk.gamma_g2),
+        delta_g2: g2_to_hex::<E>(&vk.delta_g2),
+        ic: vk.ic.iter().map(|p| g1_to_hex::<E>(p)).collect(),
+    }
+}
+
+pub fn vk_from_json<E: Engine>(json: &VerifyingKeyJson) -> anyhow::Result<VerifyingKey<E>> {
+    Ok(VerifyingKey {
+        alpha_g1: g1_from_hex::<E>(&json.alpha_g1)?,
+        // Proving-only points; unused by `prepare_verifying_key`.
+        beta_g1: E::G1Affine::zero(),
+        beta_g2: g2_from_hex::<E>(&json.beta_g2)?,
+        gamma_g2: g2_from_hex::<E>(&json.gamma_g2)?,
+        delta_g1: E::G1Affine::zero(),
+        delta_g2: g2_from_hex::<E>(&json.delta_g2)?,
+        ic: json
+            .ic
+            .iter()
+            .map(|p| g1_from_hex::<E>(p))
+            .collect::<anyhow::Result<Vec<_>>>()?,
+    })
+}
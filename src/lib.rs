@@ -1,4 +1,6 @@
-use ff::PrimeField;
+use ff::{Field, PrimeField};
+
+pub mod json;
 
 #[inline]
 pub fn get_constant<Fr: PrimeField>(scalar: u64) -> Fr {
@@ -9,3 +11,19 @@ pub fn get_constant<Fr: PrimeField>(scalar: u64) -> Fr {
     }
     x
 }
+
+/// Decodes a non-negative decimal string into a field element using Horner's
+/// method, reducing modulo the field characteristic as it goes. Returns `None`
+/// if the string contains a non-digit character. This is the field-agnostic
+/// replacement for hand-rolled `get_constant` addition loops when ingesting the
+/// already-reduced coefficients that circom emits.
+pub fn parse_field<Fr: PrimeField>(s: &str) -> Option<Fr> {
+    let ten = get_constant::<Fr>(10);
+    let mut acc = Fr::zero();
+    for c in s.trim().chars() {
+        let digit = c.to_digit(10)?;
+        acc.mul_assign(&ten);
+        acc.add_assign(&get_constant::<Fr>(u64::from(digit)));
+    }
+    Some(acc)
+}